@@ -0,0 +1,44 @@
+use winit::{dpi::PhysicalPosition, event_loop::ActiveEventLoop, keyboard::KeyCode};
+
+/// A self-contained example that `State` drives through the render loop.
+///
+/// Implementing this trait is enough to add a new demo to the crate without
+/// touching `State`: the demo owns its own pipelines/buffers and only needs
+/// to describe what device capabilities it requires and how to draw a frame.
+/// `State` queries `required_features`/`required_limits` when requesting the
+/// device, then forwards `resize`/`update`/`render` (and input) to `init`'s
+/// result for the lifetime of the window.
+pub trait Demo {
+    /// Device features this demo needs; queried before `request_device`.
+    fn required_features() -> wgpu::Features {
+        wgpu::Features::empty()
+    }
+
+    /// Device limits this demo needs; queried before `request_device`.
+    fn required_limits() -> wgpu::Limits {
+        wgpu::Limits::defaults()
+    }
+
+    fn init(
+        config: &wgpu::SurfaceConfiguration,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Self;
+
+    fn resize(
+        &mut self,
+        _config: &wgpu::SurfaceConfiguration,
+        _device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+    ) {
+    }
+
+    fn update(&mut self) {}
+
+    fn render(&mut self, view: &wgpu::TextureView, device: &wgpu::Device, queue: &wgpu::Queue);
+
+    fn handle_key(&mut self, _event_loop: &ActiveEventLoop, _code: KeyCode, _is_pressed: bool) {}
+
+    fn handle_mouse_moved(&mut self, _position: PhysicalPosition<f64>) {}
+}
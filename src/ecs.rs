@@ -0,0 +1,147 @@
+use bevy_ecs::prelude::*;
+use winit::dpi::PhysicalPosition;
+
+/// Latest cursor position in window-space pixels, fed by `CursorMoved` and
+/// consumed by systems that want to react to input without `State`/`Demo`
+/// knowing about each other.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct MousePosition(pub Option<PhysicalPosition<f64>>);
+
+/// Current surface size in pixels, refreshed from `State::update` each tick so
+/// systems can map window-space coordinates without baking in the size the
+/// window happened to open at.
+#[derive(Resource, Clone, Copy)]
+pub struct SurfaceSize(pub u32, pub u32);
+
+impl Default for SurfaceSize {
+    fn default() -> Self {
+        Self(800, 500)
+    }
+}
+
+/// Scene-level clear color, driven by [`update_clear_color_from_mouse`] on
+/// `App`'s shared `World` and mirrored into each `State`'s render `World` for
+/// [`record_render_pass`] to clear the surface with.
+#[derive(Resource, Clone, Copy)]
+pub struct ClearColor(pub wgpu::Color);
+
+impl Default for ClearColor {
+    fn default() -> Self {
+        Self(wgpu::Color {
+            r: 0.1,
+            g: 0.1,
+            b: 0.1,
+            a: 1.0,
+        })
+    }
+}
+
+fn update_clear_color_from_mouse(
+    mouse: Res<MousePosition>,
+    surface_size: Res<SurfaceSize>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    let Some(position) = mouse.0 else {
+        return;
+    };
+
+    let width = surface_size.0.max(1) as f64;
+    let height = surface_size.1.max(1) as f64;
+    clear_color.0.r = (position.x / width).clamp(0.0, 1.0);
+    clear_color.0.g = (position.y / height).clamp(0.0, 1.0);
+}
+
+/// Builds the `World` that `App` runs its schedules against, seeded with the
+/// default resources every system in this module expects.
+pub fn build_world() -> World {
+    let mut world = World::new();
+    world.init_resource::<MousePosition>();
+    world.init_resource::<SurfaceSize>();
+    world.init_resource::<ClearColor>();
+    world
+}
+
+/// Schedule run once per `update()` tick, ahead of any demo-local update.
+pub fn build_update_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(update_clear_color_from_mouse);
+    schedule
+}
+
+/// The window's wgpu device handle, inserted as a `Resource` so systems can
+/// record GPU work without `State` doing it on their behalf.
+#[derive(Resource, Clone)]
+pub struct GpuDevice(pub wgpu::Device);
+
+/// The window's wgpu queue handle, alongside [`GpuDevice`].
+#[derive(Resource, Clone)]
+pub struct GpuQueue(pub wgpu::Queue);
+
+/// The window's current surface configuration, kept in sync by `State::resize`.
+#[derive(Resource, Clone)]
+pub struct GpuSurfaceConfig(pub wgpu::SurfaceConfiguration);
+
+/// This frame's swapchain view, inserted by `State::render` immediately
+/// before running the render schedule and removed immediately after.
+#[derive(Resource)]
+pub struct RenderTarget(pub wgpu::TextureView);
+
+/// Records the frame's base render pass: acquires an encoder from [`GpuDevice`]
+/// and clears [`RenderTarget`] to [`ClearColor`], then submits it via
+/// [`GpuQueue`]. Demo-specific drawing happens afterwards, loading (not
+/// clearing) the same view.
+fn record_render_pass(
+    device: Res<GpuDevice>,
+    queue: Res<GpuQueue>,
+    clear_color: Res<ClearColor>,
+    target: Res<RenderTarget>,
+) {
+    let mut encoder = device
+        .0
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("ECS Render Pass Encoder"),
+        });
+
+    {
+        let _clear_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("ECS Clear Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.0,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear_color.0),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+    }
+
+    queue.0.submit(std::iter::once(encoder.finish()));
+}
+
+/// Builds the per-`State` `World` that `record_render_pass` runs against,
+/// seeded with this window's own GPU handles (each window has its own
+/// device/surface, so these can't live on `App`'s shared `World`).
+pub fn build_render_world(
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+) -> World {
+    let mut world = World::new();
+    world.insert_resource(GpuDevice(device));
+    world.insert_resource(GpuQueue(queue));
+    world.insert_resource(GpuSurfaceConfig(config));
+    world.init_resource::<ClearColor>();
+    world
+}
+
+/// Schedule run once per `State::render` call, ahead of `Demo::render`.
+pub fn build_render_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(record_render_pass);
+    schedule
+}
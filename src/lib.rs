@@ -21,21 +21,42 @@ use winit::{
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
-pub struct State {
+mod demo;
+mod ecs;
+mod triangle;
+mod window_state;
+
+pub use demo::Demo;
+pub use triangle::TriangleDemo;
+pub use window_state::WindowState;
+
+/// Present modes cycled through by [`State::cycle_present_mode`], in order of preference.
+const PRESENT_MODE_CYCLE: [wgpu::PresentMode; 3] = [
+    wgpu::PresentMode::Fifo,
+    wgpu::PresentMode::Mailbox,
+    wgpu::PresentMode::Immediate,
+];
+
+pub struct State<D: Demo> {
     surface: wgpu::Surface<'static>,
     device: wgpu::Device,
     queue: wgpu::Queue,
     config: wgpu::SurfaceConfiguration,
+    present_modes: Vec<wgpu::PresentMode>,
     is_surface_configured: bool,
     window: Arc<Window>,
-    clear_color: wgpu::Color,
-    render_pipeline: wgpu::RenderPipeline,
-    custom_pipeline: wgpu::RenderPipeline,
+    window_state: WindowState,
     logging: bool,
-    mouse_position: Option<winit::dpi::PhysicalPosition<f64>>,
+    demo: D,
+    clear_color: wgpu::Color,
+    /// This window's own `World`/`Schedule`, holding its own device/queue/
+    /// surface-config `Resource`s: GPU handles are per-window, so they can't
+    /// live on `App`'s shared `World` the way mouse position/clear color do.
+    render_world: bevy_ecs::world::World,
+    render_schedule: bevy_ecs::schedule::Schedule,
 }
 
-impl State {
+impl<D: Demo> State<D> {
     pub async fn new(window: Arc<Window>, logging: bool) -> anyhow::Result<Self> {
         let size = window.inner_size();
 
@@ -82,13 +103,9 @@ impl State {
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: None,
-                required_features: wgpu::Features::empty(),
+                required_features: D::required_features(),
                 experimental_features: wgpu::ExperimentalFeatures::disabled(),
-                required_limits: if cfg!(target_arch = "wasm32") {
-                    wgpu::Limits::downlevel_webgl2_defaults()
-                } else {
-                    wgpu::Limits::defaults()
-                },
+                required_limits: D::required_limits(),
                 memory_hints: Default::default(),
                 trace: wgpu::Trace::Off,
             })
@@ -125,116 +142,124 @@ impl State {
             format: surface_format,
             width: size.width,
             height: size.height,
-            // present_mode: surface_caps.present_modes[0],
             present_mode: surface_caps.present_modes[0],
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
         };
 
-        let clear_color = wgpu::Color {
-            r: 0.1,
-            g: 0.1,
-            b: 0.1,
-            a: 1.0,
-        };
+        let demo = D::init(&config, &adapter, &device, &queue);
+        let render_world = ecs::build_render_world(device.clone(), queue.clone(), config.clone());
 
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
-        });
-
-        let render_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Render Pipeline Layout"),
-                bind_group_layouts: &[],
-                push_constant_ranges: &[],
-            });
-
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                buffers: &[],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::all(),
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
-
-        let custom_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Custom Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("custom_shader.wgsl").into()),
-        });
-
-        let custom_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Drawing Pipeline"),
-            layout: None,
-            vertex: (),
-            primitive: (),
-            depth_stencil: (),
-            multisample: (),
-            fragment: (),
-            multiview: (),
-            cache: (),
-        });
-
-        let mouse_position = None;
         Ok(Self {
             surface,
             device,
             queue,
             config,
+            present_modes: surface_caps.present_modes,
             is_surface_configured: false,
             window,
-            clear_color,
-            render_pipeline,
-            custom_pipeline,
+            window_state: WindowState::empty(),
             logging,
-            mouse_position,
+            demo,
+            clear_color: ecs::ClearColor::default().0,
+            render_world,
+            render_schedule: ecs::build_render_schedule(),
         })
     }
 
-    pub fn resize(&mut self, width: u32, height: u32) {
+    pub fn window_state(&self) -> WindowState {
+        self.window_state
+    }
+
+    /// Re-queries the window for its maximized/fullscreen chrome, keeping the
+    /// currently tracked `HIDDEN` bit (which only changes on `Occluded`/`Focused`).
+    fn queried_window_state(&self) -> WindowState {
+        let mut window_state = self.window_state;
+        window_state.set(WindowState::MAXIMIZED, self.window.is_maximized());
+        window_state.set(WindowState::FULLSCREEN, self.window.fullscreen().is_some());
+        window_state
+    }
+
+    /// Records `window_state` without reconfiguring the surface; use this for
+    /// chrome transitions (e.g. `Occluded`/`Focused`) that don't change the
+    /// pixel size, as opposed to `resize`, which calls `surface.configure` for
+    /// any non-zero size.
+    fn set_window_state(&mut self, window_state: WindowState) {
+        self.window_state = window_state;
+    }
+
+    /// Reconfigures the surface with `mode` if the surface actually supports it.
+    pub fn set_present_mode(&mut self, mode: wgpu::PresentMode) {
+        if !self.present_modes.contains(&mode) {
+            return;
+        }
+
+        self.config.present_mode = mode;
+        self.surface.configure(&self.device, &self.config);
+
+        if self.logging {
+            println!("Present mode: {:?}", mode);
+        }
+    }
+
+    /// Cycles among the supported present modes in [`PRESENT_MODE_CYCLE`] order.
+    pub fn cycle_present_mode(&mut self) {
+        let supported: Vec<wgpu::PresentMode> = PRESENT_MODE_CYCLE
+            .into_iter()
+            .filter(|mode| self.present_modes.contains(mode))
+            .collect();
+
+        let Some(&first) = supported.first() else {
+            return;
+        };
+
+        // A current mode outside the cycle (e.g. `FifoRelaxed`) enters it at
+        // its first supported mode.
+        let next = match supported
+            .iter()
+            .position(|mode| *mode == self.config.present_mode)
+        {
+            Some(current) => supported[(current + 1) % supported.len()],
+            None => first,
+        };
+        self.set_present_mode(next);
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32, window_state: WindowState) {
+        self.window_state = window_state;
+
+        // While maximized/fullscreen, override the reported `width`/`height`
+        // with the window's own current size.
+        let (width, height) =
+            if window_state.intersects(WindowState::MAXIMIZED | WindowState::FULLSCREEN) {
+                let size = self.window.inner_size();
+                (size.width, size.height)
+            } else {
+                (width, height)
+            };
+
         if width > 0 && height > 0 {
             self.config.width = width;
             self.config.height = height;
+            self.config.desired_maximum_frame_latency =
+                if window_state.intersects(WindowState::MAXIMIZED | WindowState::FULLSCREEN) {
+                    1
+                } else {
+                    2
+                };
             self.surface.configure(&self.device, &self.config);
             self.is_surface_configured = true;
+            *self.render_world.resource_mut::<ecs::GpuSurfaceConfig>() =
+                ecs::GpuSurfaceConfig(self.config.clone());
+            self.demo.resize(&self.config, &self.device, &self.queue);
         }
     }
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
         self.window.request_redraw();
 
-        if !self.is_surface_configured {
+        if !self.is_surface_configured || self.window_state.contains(WindowState::HIDDEN) {
             return Ok(());
         }
 
@@ -244,88 +269,84 @@ impl State {
             .texture
             .create_view(&wgpu::wgt::TextureViewDescriptor::default());
 
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
-
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(self.clear_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.draw(0..3, 0..1);
-        }
-        self.queue.submit(std::iter::once(encoder.finish()));
+        // The render schedule records the base clear pass; `Demo::render`
+        // then draws into the same (already-cleared) view.
+        *self.render_world.resource_mut::<ecs::ClearColor>() = ecs::ClearColor(self.clear_color);
+        self.render_world.insert_resource(ecs::RenderTarget(view));
+        self.render_schedule.run(&mut self.render_world);
+        let ecs::RenderTarget(view) = self
+            .render_world
+            .remove_resource::<ecs::RenderTarget>()
+            .expect("record_render_pass must not consume its own RenderTarget resource");
+
+        self.demo.render(&view, &self.device, &self.queue);
         output.present();
 
         Ok(())
     }
 
     pub fn handle_key(&mut self, event_loop: &ActiveEventLoop, code: KeyCode, is_pressed: bool) {
-        match (code, is_pressed) {
-            (KeyCode::Escape, true) => event_loop.exit(),
-            _ => {}
+        if code == KeyCode::KeyV && is_pressed {
+            self.cycle_present_mode();
+            return;
         }
+
+        self.demo.handle_key(event_loop, code, is_pressed);
     }
 
     pub fn handle_mouse_moved(&mut self, position: winit::dpi::PhysicalPosition<f64>) {
-        let size = self.window.inner_size();
-        let r = position.x / size.width as f64;
-        let g = position.y / size.height as f64;
-
-        self.clear_color = wgpu::Color {
-            r,
-            g,
-            b: self.clear_color.b,
-            a: self.clear_color.a,
-        };
+        self.demo.handle_mouse_moved(position);
     }
 
-    pub fn handle_mouse_moved2(&mut self, position: winit::dpi::PhysicalPosition<f64>) {
-        self.mouse_position = Some(position);
+    /// Runs `update_schedule` against `world`, stashing the resulting
+    /// scene-level clear color for the next `render` call, then runs the
+    /// demo's own per-frame update.
+    pub fn update(
+        &mut self,
+        world: &mut bevy_ecs::world::World,
+        update_schedule: &mut bevy_ecs::schedule::Schedule,
+    ) {
+        *world.resource_mut::<ecs::SurfaceSize>() =
+            ecs::SurfaceSize(self.config.width, self.config.height);
+        update_schedule.run(world);
+
+        self.clear_color = world.resource::<ecs::ClearColor>().0;
+        self.demo.update();
     }
 
-    pub fn update(&mut self) {
-        // later
+    pub fn window_id(&self) -> winit::window::WindowId {
+        self.window.id()
     }
 }
 
 pub struct App {
     #[cfg(target_arch = "wasm32")]
-    proxy: Option<winit::event_loop::EventLoopProxy<State>>,
-    state: Option<State>,
-    state2: Option<State>,
+    proxy: Option<winit::event_loop::EventLoopProxy<State<TriangleDemo>>>,
+    states: Vec<State<TriangleDemo>>,
+    /// Scene state (clear color, mouse position, ...) lives here instead of on
+    /// `State`/`Demo` directly, so new drawables can be added as entities
+    /// rather than hardcoded fields. GPU resources stay per-window on `State`:
+    /// each window owns its own device/surface, so they can't be singular
+    /// `World` resources the way a single-window engine's would be.
+    world: bevy_ecs::world::World,
+    update_schedule: bevy_ecs::schedule::Schedule,
 }
 
 impl App {
-    pub fn new(#[cfg(target_arch = "wasm32")] event_loop: &EventLoop<State>) -> Self {
+    pub fn new(#[cfg(target_arch = "wasm32")] event_loop: &EventLoop<State<TriangleDemo>>) -> Self {
         #[cfg(target_arch = "wasm32")]
         let proxy = Some(event_loop.create_proxy());
         Self {
-            state: None,
-            state2: None,
+            states: Vec::new(),
             #[cfg(target_arch = "wasm32")]
             proxy,
+            world: ecs::build_world(),
+            update_schedule: ecs::build_update_schedule(),
         }
     }
 }
 
-impl ApplicationHandler<State> for App {
+impl ApplicationHandler<State<TriangleDemo>> for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         #[allow(unused_mut)]
         let mut window_attributes = Window::default_attributes()
@@ -357,74 +378,93 @@ impl ApplicationHandler<State> for App {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
-            self.state = Some(match pollster::block_on(State::new(window, true)) {
+            let state = match pollster::block_on(State::<TriangleDemo>::new(window, true)) {
                 Ok(state) => state,
                 Err(e) => {
                     println!("Error: {}", e);
                     exit(1)
                 }
-            });
-            self.state2 = Some(match pollster::block_on(State::new(window2, true)) {
+            };
+            let state2 = match pollster::block_on(State::<TriangleDemo>::new(window2, true)) {
                 Ok(state) => state,
                 Err(e) => {
                     println!("Error: {}", e);
                     exit(1)
                 }
-            });
+            };
+            self.states.push(state);
+            self.states.push(state2);
         }
 
         #[cfg(target_arch = "wasm32")]
         {
             if let Some(proxy) = self.proxy.take() {
                 wasm_bindgen_futures::spawn_local(async move {
-                    assert!(
-                        proxy
-                            .send_event(
-                                State::new(window).await.expect("Unable to Create Canvas!!")
-                            )
-                            .is_ok()
-                    )
+                    assert!(proxy
+                        .send_event(
+                            State::<TriangleDemo>::new(window)
+                                .await
+                                .expect("Unable to Create Canvas!!")
+                        )
+                        .is_ok())
                 })
             }
         }
     }
 
     #[allow(unused_mut)]
-    fn user_event(&mut self, _event_loop: &ActiveEventLoop, mut event: State) {
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, mut event: State<TriangleDemo>) {
         #[cfg(target_arch = "wasm32")]
         {
             event.window.request_redraw();
             event.resize(
                 event.window.inner_size().width,
                 event.window.inner_size().height,
+                WindowState::empty(),
             );
         }
-        self.state = Some(event);
+        self.states.push(event);
     }
 
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
-        _window_id: winit::window::WindowId,
+        window_id: winit::window::WindowId,
         event: WindowEvent,
     ) {
-        let state = match &mut self.state {
-            Some(canvas) => canvas,
-            None => return,
+        if let WindowEvent::CloseRequested = event {
+            self.states.retain(|s| s.window_id() != window_id);
+            if self.states.is_empty() {
+                event_loop.exit();
+            }
+            return;
+        }
+
+        if let WindowEvent::CursorMoved { position, .. } = event {
+            self.world.resource_mut::<ecs::MousePosition>().0 = Some(position);
+        }
+
+        // Borrowing `self.states` directly (rather than through a `&mut self`
+        // helper) keeps this disjoint from `self.world`/`self.update_schedule`
+        // below, which `RedrawRequested` needs at the same time as `state`.
+        let Some(state) = self.states.iter_mut().find(|s| s.window_id() == window_id) else {
+            return;
         };
 
         match event {
-            WindowEvent::CloseRequested => {
-                println!("fuck you");
+            WindowEvent::Resized(size) => {
+                let window_state = state.queried_window_state();
+                state.resize(size.width, size.height, window_state);
             }
-            WindowEvent::Resized(size) => state.resize(size.width, size.height),
             WindowEvent::RedrawRequested => {
-                state.update();
+                state.update(&mut self.world, &mut self.update_schedule);
+
                 match state.render() {
                     Ok(_) => {}
                     Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
                         let size = state.window.inner_size();
-                        state.resize(size.width, size.height);
+                        let window_state = state.queried_window_state();
+                        state.resize(size.width, size.height, window_state);
                     }
                     Err(e) => {
                         log::error!("Unable to render {}", e);
@@ -440,9 +480,17 @@ impl ApplicationHandler<State> for App {
                     },
                 ..
             } => state.handle_key(event_loop, code, key_state.is_pressed()),
-            // WindowEvent::CursorMoved { position, .. } => state.handle_mouse_moved(position),
-            WindowEvent::CursorMoved { position, .. } => state.handle_mouse_moved2(position),
-            // WindowEvent::CursorMoved { position, .. } => {}
+            WindowEvent::CursorMoved { position, .. } => state.handle_mouse_moved(position),
+            WindowEvent::Occluded(occluded) => {
+                let mut window_state = state.queried_window_state();
+                window_state.set(WindowState::HIDDEN, occluded);
+                state.set_window_state(window_state);
+            }
+            WindowEvent::Focused(true) => {
+                let mut window_state = state.queried_window_state();
+                window_state.remove(WindowState::HIDDEN);
+                state.set_window_state(window_state);
+            }
             _ => {}
         };
     }
@@ -0,0 +1,320 @@
+use wgpu::util::DeviceExt;
+use winit::{dpi::PhysicalPosition, event_loop::ActiveEventLoop, keyboard::KeyCode};
+
+use crate::demo::Demo;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Vertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex {
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+            ],
+        }
+    }
+}
+
+const VERTICES: &[Vertex] = &[
+    Vertex {
+        position: [0.0, 0.5, 0.0],
+        color: [1.0, 0.0, 0.0],
+    },
+    Vertex {
+        position: [-0.5, -0.5, 0.0],
+        color: [0.0, 1.0, 0.0],
+    },
+    Vertex {
+        position: [0.5, -0.5, 0.0],
+        color: [0.0, 0.0, 1.0],
+    },
+];
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct MouseUniform {
+    position: [f32; 2],
+}
+
+/// Which pipeline [`TriangleDemo::render`] draws with, toggled via [`KeyCode::KeyC`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputMode {
+    Triangle,
+    Custom,
+}
+
+/// The original hardcoded triangle example, now living behind the [`Demo`]
+/// trait instead of being baked directly into `State`.
+pub struct TriangleDemo {
+    render_pipeline: wgpu::RenderPipeline,
+    custom_pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    mouse_uniform_buffer: wgpu::Buffer,
+    mouse_bind_group: wgpu::BindGroup,
+    mouse_position: Option<PhysicalPosition<f64>>,
+    size: (u32, u32),
+    input_mode: InputMode,
+}
+
+impl Demo for TriangleDemo {
+    fn required_limits() -> wgpu::Limits {
+        if cfg!(target_arch = "wasm32") {
+            wgpu::Limits::downlevel_webgl2_defaults()
+        } else {
+            wgpu::Limits::defaults()
+        }
+    }
+
+    fn init(
+        config: &wgpu::SurfaceConfiguration,
+        _adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[Vertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::all(),
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let custom_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Custom Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("custom_shader.wgsl").into()),
+        });
+
+        let mouse_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mouse Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[MouseUniform {
+                position: [0.0, 0.0],
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let mouse_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Mouse Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+
+        let mouse_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Mouse Bind Group"),
+            layout: &mouse_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: mouse_uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let custom_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Drawing Pipeline Layout"),
+                bind_group_layouts: &[&mouse_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+
+        let custom_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Drawing Pipeline"),
+            layout: Some(&custom_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &custom_shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &custom_shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::all(),
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Vertex Buffer"),
+            contents: bytemuck::cast_slice(VERTICES),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        Self {
+            render_pipeline,
+            custom_pipeline,
+            vertex_buffer,
+            mouse_uniform_buffer,
+            mouse_bind_group,
+            mouse_position: None,
+            size: (config.width, config.height),
+            input_mode: InputMode::Triangle,
+        }
+    }
+
+    fn resize(
+        &mut self,
+        config: &wgpu::SurfaceConfiguration,
+        _device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+    ) {
+        self.size = (config.width, config.height);
+    }
+
+    fn render(&mut self, view: &wgpu::TextureView, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.input_mode == InputMode::Custom {
+            if let Some(position) = self.mouse_position {
+                let (width, height) = self.size;
+                let ndc_x = (position.x / width as f64) * 2.0 - 1.0;
+                let ndc_y = 1.0 - (position.y / height as f64) * 2.0;
+                queue.write_buffer(
+                    &self.mouse_uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[MouseUniform {
+                        position: [ndc_x as f32, ndc_y as f32],
+                    }]),
+                );
+            }
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Render Encoder"),
+        });
+
+        {
+            // `State::render` already recorded a pass that cleared `view` via
+            // the ECS render schedule; load instead of clearing again here.
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            match self.input_mode {
+                InputMode::Triangle => {
+                    render_pass.set_pipeline(&self.render_pipeline);
+                    render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                    render_pass.draw(0..VERTICES.len() as u32, 0..1);
+                }
+                InputMode::Custom => {
+                    render_pass.set_pipeline(&self.custom_pipeline);
+                    render_pass.set_bind_group(0, &self.mouse_bind_group, &[]);
+                    render_pass.draw(0..3, 0..1);
+                }
+            }
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    fn handle_key(&mut self, event_loop: &ActiveEventLoop, code: KeyCode, is_pressed: bool) {
+        match (code, is_pressed) {
+            (KeyCode::Escape, true) => event_loop.exit(),
+            (KeyCode::KeyC, true) => {
+                self.input_mode = match self.input_mode {
+                    InputMode::Triangle => InputMode::Custom,
+                    InputMode::Custom => InputMode::Triangle,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_mouse_moved(&mut self, position: PhysicalPosition<f64>) {
+        self.mouse_position = Some(position);
+    }
+}
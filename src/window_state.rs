@@ -0,0 +1,12 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// High-level window chrome state, tracked alongside the raw pixel size so
+    /// `State` can tell *why* a resize happened instead of just the new size.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct WindowState: u8 {
+        const MAXIMIZED = 1 << 0;
+        const FULLSCREEN = 1 << 1;
+        const HIDDEN = 1 << 2;
+    }
+}